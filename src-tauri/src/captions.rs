@@ -0,0 +1,160 @@
+use crate::managers::history::TranscriptWord;
+
+/// Words are grouped into a caption cue once they reach this length or end on
+/// sentence-ending punctuation, whichever comes first.
+const MAX_WORDS_PER_CUE: usize = 7;
+
+struct Cue<'a> {
+    words: Vec<&'a TranscriptWord>,
+}
+
+impl<'a> Cue<'a> {
+    fn start_ms(&self) -> u64 {
+        self.words.first().map(|w| w.start_ms).unwrap_or(0)
+    }
+
+    fn end_ms(&self) -> u64 {
+        self.words.last().map(|w| w.end_ms).unwrap_or(0)
+    }
+
+    fn text(&self) -> String {
+        self.words
+            .iter()
+            .map(|w| w.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+fn ends_sentence(word: &str) -> bool {
+    word.ends_with(['.', '!', '?'])
+}
+
+fn group_into_cues(words: &[TranscriptWord]) -> Vec<Cue<'_>> {
+    let mut cues = Vec::new();
+    let mut current: Vec<&TranscriptWord> = Vec::new();
+
+    for word in words {
+        current.push(word);
+        if current.len() >= MAX_WORDS_PER_CUE || ends_sentence(&word.text) {
+            cues.push(Cue { words: std::mem::take(&mut current) });
+        }
+    }
+
+    if !current.is_empty() {
+        cues.push(Cue { words: current });
+    }
+
+    cues
+}
+
+fn format_srt_timestamp(ms: u64) -> String {
+    let hours = ms / 3_600_000;
+    let minutes = (ms % 3_600_000) / 60_000;
+    let seconds = (ms % 60_000) / 1000;
+    let millis = ms % 1000;
+    format!("{:02}:{:02}:{:02},{:03}", hours, minutes, seconds, millis)
+}
+
+fn format_vtt_timestamp(ms: u64) -> String {
+    let hours = ms / 3_600_000;
+    let minutes = (ms % 3_600_000) / 60_000;
+    let seconds = (ms % 60_000) / 1000;
+    let millis = ms % 1000;
+    format!("{:02}:{:02}:{:02}.{:03}", hours, minutes, seconds, millis)
+}
+
+/// Renders `words` as a sequentially-numbered SRT caption track.
+pub fn to_srt(words: &[TranscriptWord]) -> String {
+    let mut out = String::new();
+    for (index, cue) in group_into_cues(words).into_iter().enumerate() {
+        out.push_str(&format!("{}\n", index + 1));
+        out.push_str(&format!(
+            "{} --> {}\n",
+            format_srt_timestamp(cue.start_ms()),
+            format_srt_timestamp(cue.end_ms())
+        ));
+        out.push_str(&cue.text());
+        out.push_str("\n\n");
+    }
+    out
+}
+
+/// Renders `words` as a WebVTT caption track.
+pub fn to_webvtt(words: &[TranscriptWord]) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for cue in group_into_cues(words) {
+        out.push_str(&format!(
+            "{} --> {}\n",
+            format_vtt_timestamp(cue.start_ms()),
+            format_vtt_timestamp(cue.end_ms())
+        ));
+        out.push_str(&cue.text());
+        out.push_str("\n\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start_ms: u64, end_ms: u64) -> TranscriptWord {
+        TranscriptWord {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+        }
+    }
+
+    #[test]
+    fn formats_srt_timestamp() {
+        assert_eq!(format_srt_timestamp(0), "00:00:00,000");
+        assert_eq!(format_srt_timestamp(1_234), "00:00:01,234");
+        assert_eq!(format_srt_timestamp(3_661_001), "01:01:01,001");
+    }
+
+    #[test]
+    fn formats_vtt_timestamp() {
+        assert_eq!(format_vtt_timestamp(0), "00:00:00.000");
+        assert_eq!(format_vtt_timestamp(1_234), "00:00:01.234");
+        assert_eq!(format_vtt_timestamp(3_661_001), "01:01:01.001");
+    }
+
+    #[test]
+    fn groups_cues_on_sentence_punctuation() {
+        let words = vec![word("Hi.", 0, 500), word("Bye.", 500, 1000)];
+        let cues = group_into_cues(&words);
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].text(), "Hi.");
+        assert_eq!(cues[1].text(), "Bye.");
+    }
+
+    #[test]
+    fn groups_cues_at_max_words() {
+        let words: Vec<TranscriptWord> = (0..9)
+            .map(|i| word(&format!("w{}", i), i * 100, i * 100 + 100))
+            .collect();
+        let cues = group_into_cues(&words);
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].words.len(), MAX_WORDS_PER_CUE);
+        assert_eq!(cues[1].words.len(), 2);
+    }
+
+    #[test]
+    fn srt_numbers_cues_sequentially() {
+        let words = vec![word("Hi.", 0, 500), word("Bye.", 500, 1_200)];
+        let srt = to_srt(&words);
+        assert_eq!(
+            srt,
+            "1\n00:00:00,000 --> 00:00:00,500\nHi.\n\n2\n00:00:00,500 --> 00:00:01,200\nBye.\n\n"
+        );
+    }
+
+    #[test]
+    fn webvtt_has_header_and_dot_separated_millis() {
+        let words = vec![word("Hi.", 0, 500)];
+        let vtt = to_webvtt(&words);
+        assert_eq!(vtt, "WEBVTT\n\n00:00:00.000 --> 00:00:00.500\nHi.\n\n");
+    }
+}
@@ -1,4 +1,5 @@
-use crate::managers::history::{HistoryEntry, HistoryManager};
+use crate::captions;
+use crate::managers::history::{HistoryEntry, HistoryManager, HistorySearchFilters};
 use crate::gemini_client;
 use crate::settings;
 use std::sync::Arc;
@@ -116,6 +117,41 @@ pub async fn update_recording_retention_period(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn search_history_entries(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    query: String,
+    filters: HistorySearchFilters,
+) -> Result<Vec<HistoryEntry>, String> {
+    history_manager
+        .search_entries(&query, filters)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn export_history_entry(
+    _app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    format: String,
+) -> Result<String, String> {
+    let entry = history_manager
+        .get_entry(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("No history entry with id {}", id))?;
+
+    match format.as_str() {
+        "srt" => Ok(captions::to_srt(&entry.transcript)),
+        "webvtt" | "vtt" => Ok(captions::to_webvtt(&entry.transcript)),
+        _ => Err(format!("Unsupported export format: {}", format)),
+    }
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn translate_history_entry(
@@ -125,11 +161,38 @@ pub async fn translate_history_entry(
 ) -> Result<String, String> {
     // Get Gemini API key from settings
     let settings = settings::get_settings(&app);
-    let api_key = settings
-        .post_process_api_keys
-        .get("gemini_transcription")
-        .ok_or_else(|| "Gemini API key not configured".to_string())?;
+    if settings.gemini_api_key.is_empty() {
+        return Err("Gemini API key not configured".to_string());
+    }
 
     // Call Gemini translate function
-    gemini_client::translate_text(api_key, &text).await
+    gemini_client::translate_text(&settings.gemini_api_key, &text).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn post_process_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    action_id: String,
+) -> Result<String, String> {
+    let entry = history_manager
+        .get_entry(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("No history entry with id {}", id))?;
+
+    let settings = settings::get_settings(&app);
+    let action = settings
+        .post_process_actions
+        .iter()
+        .find(|a| a.id == action_id)
+        .ok_or_else(|| format!("No post-processing action with id {}", action_id))?;
+
+    if settings.gemini_api_key.is_empty() {
+        return Err("Gemini API key not configured".to_string());
+    }
+
+    gemini_client::run_prompt(&settings.gemini_api_key, &action.model, &action.prompt, &entry.text).await
 }
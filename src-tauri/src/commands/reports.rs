@@ -0,0 +1,18 @@
+use crate::reports;
+use tauri::AppHandle;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_failure_reports(_app: AppHandle) -> Result<Vec<String>, String> {
+    let paths = reports::list_reports()?;
+    Ok(paths
+        .into_iter()
+        .filter_map(|p| p.to_str().map(str::to_string))
+        .collect())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn open_failure_report(_app: AppHandle, path: String) -> Result<String, String> {
+    reports::open_report(std::path::Path::new(&path))
+}
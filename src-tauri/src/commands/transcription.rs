@@ -0,0 +1,66 @@
+use crate::managers::history::HistoryManager;
+use crate::settings;
+use crate::streaming_transcription;
+use crate::transcription_backend::transcribe_with_timestamps_with_settings;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, State};
+
+/// Number of consecutive candidates a streamed token must survive unchanged
+/// before it is emitted to the frontend as final.
+const STREAMING_STABILITY: usize = 3;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn transcribe_audio(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    samples: Vec<f32>,
+    sample_rate: u32,
+    language_hint: Option<String>,
+    audio_file_name: String,
+) -> Result<String, String> {
+    let settings = settings::get_settings(&app);
+    let (text, transcript) = transcribe_with_timestamps_with_settings(
+        &settings,
+        samples,
+        sample_rate,
+        language_hint.clone(),
+    )
+    .await?;
+
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0);
+
+    history_manager
+        .add_entry(text.clone(), audio_file_name, created_at, transcript, language_hint)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(text)
+}
+
+/// Streams a transcription of `samples`, emitting `transcription-partial`
+/// events as stabilized chunks of text become available instead of waiting
+/// for the whole clip to finish.
+#[tauri::command]
+#[specta::specta]
+pub async fn transcribe_audio_streaming(
+    app: AppHandle,
+    samples: Vec<f32>,
+    sample_rate: u32,
+    language_hint: Option<String>,
+) -> Result<(), String> {
+    let settings = settings::get_settings(&app);
+    streaming_transcription::stream_transcribe(
+        &app,
+        &settings.gemini_api_key,
+        samples,
+        sample_rate,
+        language_hint,
+        STREAMING_STABILITY,
+    )
+    .await
+}
@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use log::{debug, error, info};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use serde::Deserialize;
+
+use crate::gemini_client::samples_to_wav_bytes;
+use crate::transcription_backend::TranscriptionBackend;
+
+const DEEPGRAM_API_BASE: &str = "https://api.deepgram.com/v1/listen";
+
+#[derive(Debug, Deserialize)]
+struct DeepgramResponse {
+    results: Option<DeepgramResults>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramResults {
+    channels: Vec<DeepgramChannel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramChannel {
+    alternatives: Vec<DeepgramAlternative>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepgramAlternative {
+    transcript: String,
+}
+
+/// Transcribes audio samples using Deepgram's prerecorded endpoint
+///
+/// # Arguments
+/// * `api_key` - The Deepgram API key
+/// * `samples` - Audio samples as f32 values (normalized to -1.0 to 1.0)
+/// * `sample_rate` - The sample rate the audio was captured at
+/// * `language_hint` - Optional language hint for transcription
+///
+/// # Returns
+/// The transcribed text or an error
+pub async fn transcribe_audio(
+    api_key: &str,
+    samples: Vec<f32>,
+    sample_rate: u32,
+    language_hint: Option<String>,
+) -> Result<String, String> {
+    if api_key.is_empty() {
+        return Err(
+            "Deepgram API key is not configured. Please add your API key in Settings."
+                .to_string(),
+        );
+    }
+
+    if samples.is_empty() {
+        return Ok(String::new());
+    }
+
+    info!("Starting Deepgram transcription with {} samples", samples.len());
+
+    let wav_bytes = samples_to_wav_bytes(&samples, sample_rate)?;
+    debug!("Converted to WAV: {} bytes", wav_bytes.len());
+
+    let mut url = format!("{}?model=nova-2&smart_format=true", DEEPGRAM_API_BASE);
+    if let Some(lang) = language_hint.filter(|lang| lang != "auto") {
+        url.push_str(&format!("&language={}", lang));
+    }
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        AUTHORIZATION,
+        HeaderValue::from_str(&format!("Token {}", api_key))
+            .map_err(|e| format!("Invalid API key: {}", e))?,
+    );
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("audio/wav"));
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(&url)
+        .headers(headers)
+        .body(wav_bytes)
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    let status = response.status();
+    let response_text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    if !status.is_success() {
+        error!("Deepgram transcription failed with status {}: {}", status, response_text);
+        return Err(format!("API request failed with status {}: {}", status, response_text));
+    }
+
+    let deepgram_response: DeepgramResponse = serde_json::from_str(&response_text)
+        .map_err(|e| format!("Failed to parse response: {} - Response: {}", e, response_text))?;
+
+    let text = deepgram_response
+        .results
+        .and_then(|r| r.channels.into_iter().next())
+        .and_then(|c| c.alternatives.into_iter().next())
+        .map(|a| a.transcript)
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    info!("Deepgram transcription succeeded: {} chars", text.len());
+
+    Ok(text)
+}
+
+/// [`TranscriptionBackend`] implementation backed by the Deepgram API.
+pub struct DeepgramBackend {
+    api_key: String,
+}
+
+impl DeepgramBackend {
+    pub fn new(api_key: String) -> Self {
+        Self { api_key }
+    }
+}
+
+#[async_trait]
+impl TranscriptionBackend for DeepgramBackend {
+    async fn transcribe(
+        &self,
+        samples: Vec<f32>,
+        sample_rate: u32,
+        language_hint: Option<String>,
+    ) -> Result<String, String> {
+        transcribe_audio(&self.api_key, samples, sample_rate, language_hint).await
+    }
+}
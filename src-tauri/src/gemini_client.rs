@@ -5,6 +5,8 @@ use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
 use serde::{Deserialize, Serialize};
 use std::io::Cursor;
 
+use crate::managers::history::TranscriptWord;
+
 const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta";
 
 #[derive(Debug, Serialize)]
@@ -57,8 +59,25 @@ struct GeminiError {
     code: Option<i32>,
 }
 
+/// Error from a single model attempt against the Gemini API, carrying enough
+/// detail (HTTP status, raw body) to populate a [`crate::reports::FailureReport`].
+#[derive(Debug, Clone)]
+struct RequestError {
+    status: Option<u16>,
+    body: String,
+}
+
+impl RequestError {
+    fn message(&self) -> String {
+        match self.status {
+            Some(status) => format!("API request failed with status {}: {}", status, self.body),
+            None => self.body.clone(),
+        }
+    }
+}
+
 /// Converts f32 audio samples (range -1.0 to 1.0) to WAV bytes
-fn samples_to_wav_bytes(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, String> {
+pub(crate) fn samples_to_wav_bytes(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, String> {
     let spec = WavSpec {
         channels: 1,
         sample_rate,
@@ -87,80 +106,224 @@ fn samples_to_wav_bytes(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, St
     Ok(cursor.into_inner())
 }
 
-/// Transcribes audio samples using Gemini API
-///
-/// # Arguments
-/// * `api_key` - The Gemini API key
-/// * `samples` - Audio samples as f32 values (normalized to -1.0 to 1.0)
-/// * `language_hint` - Optional language hint for transcription
-///
-/// # Returns
-/// The transcribed text or an error
-pub async fn transcribe_audio(
+async fn send_transcription_request(
+    api_key: &str,
+    model: &str,
+    base64_audio: &str,
+    prompt: &str,
+) -> Result<String, RequestError> {
+    let url = format!(
+        "{}/models/{}:generateContent?key={}",
+        GEMINI_API_BASE, model, api_key
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    let request_body = GeminiRequest {
+        contents: vec![Content {
+            parts: vec![
+                Part::Text {
+                    text: prompt.to_string(),
+                },
+                Part::InlineData {
+                    inline_data: InlineData {
+                        mime_type: "audio/wav".to_string(),
+                        data: base64_audio.to_string(),
+                    },
+                },
+            ],
+        }],
+    };
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(&url)
+        .headers(headers)
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| RequestError {
+            status: None,
+            body: format!("HTTP request failed: {}", e),
+        })?;
+
+    let status = response.status();
+    let response_text = response.text().await.map_err(|e| RequestError {
+        status: Some(status.as_u16()),
+        body: format!("Failed to read response: {}", e),
+    })?;
+
+    if !status.is_success() {
+        return Err(RequestError {
+            status: Some(status.as_u16()),
+            body: response_text,
+        });
+    }
+
+    let gemini_response: GeminiResponse =
+        serde_json::from_str(&response_text).map_err(|e| RequestError {
+            status: Some(status.as_u16()),
+            body: format!("{} - Response: {}", e, response_text),
+        })?;
+
+    if let Some(error) = gemini_response.error {
+        return Err(RequestError {
+            status: Some(status.as_u16()),
+            body: format!(
+                "Gemini API error (code {:?}): {} - Response: {}",
+                error.code, error.message, response_text
+            ),
+        });
+    }
+
+    // Extract text from the response
+    let text = gemini_response
+        .candidates
+        .and_then(|c| c.into_iter().next())
+        .and_then(|c| c.content)
+        .and_then(|c| c.parts)
+        .and_then(|p| p.into_iter().next())
+        .and_then(|p| p.text)
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+
+    Ok(text)
+}
+
+#[derive(Debug, Deserialize)]
+struct TimedWord {
+    word: String,
+    start: f64,
+    end: f64,
+}
+
+/// Transcribes audio and asks Gemini to additionally return word-level
+/// timestamps as JSON, for use by [`crate::captions`]. Falls back to the raw
+/// response as plain text (with no timestamps) if it can't be parsed as JSON.
+pub async fn transcribe_with_timestamps(
     api_key: &str,
     samples: Vec<f32>,
+    sample_rate: u32,
     language_hint: Option<String>,
-) -> Result<String, String> {
+) -> Result<(String, Vec<TranscriptWord>), String> {
     if api_key.is_empty() {
         return Err("Gemini API key is not configured. Please add your API key in Settings.".to_string());
     }
 
     if samples.is_empty() {
-        return Ok(String::new());
+        return Ok((String::new(), Vec::new()));
     }
 
-    info!("Starting Gemini transcription with {} samples", samples.len());
+    info!("Starting Gemini timestamped transcription with {} samples", samples.len());
 
-    // Convert samples to WAV bytes (assuming 16kHz sample rate, which is what the app uses)
-    let wav_bytes = samples_to_wav_bytes(&samples, 16000)?;
-    debug!("Converted to WAV: {} bytes", wav_bytes.len());
-
-    // Base64 encode the WAV data
+    let wav_bytes = samples_to_wav_bytes(&samples, sample_rate)?;
     let base64_audio = STANDARD.encode(&wav_bytes);
 
-    // Build the prompt
-    let prompt = match language_hint {
-        Some(ref lang) if lang != "auto" => {
-            format!(
-                "Transcribe this audio. The language is {}. Return only the transcribed text, nothing else.",
-                lang
-            )
-        }
-        _ => "Transcribe this audio. Return only the transcribed text, nothing else.".to_string(),
+    let language_clause = match language_hint {
+        Some(ref lang) if lang != "auto" => format!(" The language is {}.", lang),
+        _ => String::new(),
     };
+    let prompt = format!(
+        "Transcribe this audio.{} Return ONLY a JSON array of objects with the shape \
+         {{\"word\": string, \"start\": number, \"end\": number}}, where start/end are \
+         seconds from the beginning of the audio. Do not include any other text.",
+        language_clause
+    );
 
-    // Try primary model first, then fallback
     let models = ["gemini-2.5-flash", "gemini-2.0-flash"];
-    let mut last_error = String::new();
+    let mut last_error: Option<RequestError> = None;
 
     for model in &models {
-        debug!("Attempting transcription with model: {}", model);
+        debug!("Attempting timestamped transcription with model: {}", model);
 
         match send_transcription_request(api_key, model, &base64_audio, &prompt).await {
-            Ok(text) => {
+            Ok(raw) => {
+                let (text, words) = parse_timed_words(&raw);
                 info!(
-                    "Gemini transcription succeeded with model {}: {} chars",
+                    "Gemini timestamped transcription succeeded with model {}: {} words",
                     model,
-                    text.len()
+                    words.len()
                 );
-                return Ok(text);
+                return Ok((text, words));
             }
             Err(e) => {
-                error!("Gemini transcription failed with model {}: {}", model, e);
-                last_error = e;
+                error!(
+                    "Gemini timestamped transcription failed with model {}: {}",
+                    model,
+                    e.message()
+                );
+                last_error = Some(e);
             }
         }
     }
 
-    Err(format!("Gemini transcription failed: {}", last_error))
+    let last_error = last_error.unwrap_or_else(|| RequestError {
+        status: None,
+        body: "unknown error".to_string(),
+    });
+
+    let report = crate::reports::FailureReport::new(
+        models.iter().map(|m| m.to_string()).collect(),
+        last_error.status,
+        last_error.body.clone(),
+        prompt.clone(),
+        samples.len(),
+        sample_rate,
+    );
+    match crate::reports::write_report(&report) {
+        Ok(path) => info!("Wrote transcription failure report to {}", path.display()),
+        Err(e) => error!("Failed to write transcription failure report: {}", e),
+    }
+
+    Err(format!("Gemini transcription failed: {}", last_error.message()))
 }
 
-async fn send_transcription_request(
+/// Parses a JSON array of timed words into plain text plus structured words,
+/// falling back to treating `raw` as already-plain text if it isn't valid JSON.
+fn parse_timed_words(raw: &str) -> (String, Vec<TranscriptWord>) {
+    let cleaned = raw
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+
+    match serde_json::from_str::<Vec<TimedWord>>(cleaned) {
+        Ok(words) => {
+            let text = words
+                .iter()
+                .map(|w| w.word.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            let transcript = words
+                .into_iter()
+                .map(|w| TranscriptWord {
+                    text: w.word,
+                    start_ms: (w.start * 1000.0).round() as u64,
+                    end_ms: (w.end * 1000.0).round() as u64,
+                })
+                .collect();
+            (text, transcript)
+        }
+        Err(_) => (raw.trim().to_string(), Vec::new()),
+    }
+}
+
+/// Runs a single text prompt against a Gemini model and returns the text of
+/// the first part of the first candidate, trimmed. This is the shared code path behind every text-to-text
+/// post-processing action (translate, summarize, fix punctuation, ...).
+pub async fn run_prompt(
     api_key: &str,
     model: &str,
-    base64_audio: &str,
-    prompt: &str,
+    system_prompt: &str,
+    user_text: &str,
 ) -> Result<String, String> {
+    if api_key.is_empty() {
+        return Err("Gemini API key is not configured. Please add your API key in Settings.".to_string());
+    }
+
     let url = format!(
         "{}/models/{}:generateContent?key={}",
         GEMINI_API_BASE, model, api_key
@@ -171,17 +334,9 @@ async fn send_transcription_request(
 
     let request_body = GeminiRequest {
         contents: vec![Content {
-            parts: vec![
-                Part::Text {
-                    text: prompt.to_string(),
-                },
-                Part::InlineData {
-                    inline_data: InlineData {
-                        mime_type: "audio/wav".to_string(),
-                        data: base64_audio.to_string(),
-                    },
-                },
-            ],
+            parts: vec![Part::Text {
+                text: format!("{}\n\n{}", system_prompt, user_text),
+            }],
         }],
     };
 
@@ -214,7 +369,6 @@ async fn send_transcription_request(
         ));
     }
 
-    // Extract text from the response
     let text = gemini_response
         .candidates
         .and_then(|c| c.into_iter().next())
@@ -228,3 +382,17 @@ async fn send_transcription_request(
 
     Ok(text)
 }
+
+/// Translates `text` to English using Gemini. Kept as a thin wrapper over
+/// [`run_prompt`] so the translate action shares its error handling and
+/// request plumbing with every other post-processing action.
+pub async fn translate_text(api_key: &str, text: &str) -> Result<String, String> {
+    run_prompt(
+        api_key,
+        "gemini-2.0-flash",
+        "Translate the following text to English. Return only the translation, nothing else.",
+        text,
+    )
+    .await
+}
+
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+
+/// A single word from a transcript, anchored to its position in the audio.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptWord {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: i64,
+    pub text: String,
+    pub audio_file_name: String,
+    pub created_at: i64,
+    pub saved: bool,
+    #[serde(default)]
+    pub transcript: Vec<TranscriptWord>,
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+/// Optional narrowing applied on top of a [`HistoryManager::search_entries`]
+/// text query.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HistorySearchFilters {
+    #[serde(default)]
+    pub saved_only: bool,
+    #[serde(default)]
+    pub start_ms: Option<i64>,
+    #[serde(default)]
+    pub end_ms: Option<i64>,
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+pub struct HistoryManager {
+    entries: Mutex<Vec<HistoryEntry>>,
+    audio_dir: PathBuf,
+    next_id: AtomicI64,
+}
+
+impl HistoryManager {
+    pub fn new(audio_dir: PathBuf) -> Self {
+        Self {
+            entries: Mutex::new(Vec::new()),
+            audio_dir,
+            next_id: AtomicI64::new(1),
+        }
+    }
+
+    /// Inserts a new history entry and returns its assigned id.
+    pub async fn add_entry(
+        &self,
+        text: String,
+        audio_file_name: String,
+        created_at: i64,
+        transcript: Vec<TranscriptWord>,
+        language: Option<String>,
+    ) -> Result<i64, String> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let entry = HistoryEntry {
+            id,
+            text,
+            audio_file_name,
+            created_at,
+            saved: false,
+            transcript,
+            language,
+        };
+
+        self.entries.lock().map_err(|e| e.to_string())?.push(entry);
+        Ok(id)
+    }
+
+    pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>, String> {
+        Ok(self.entries.lock().map_err(|e| e.to_string())?.clone())
+    }
+
+    pub async fn toggle_saved_status(&self, id: i64) -> Result<(), String> {
+        let mut entries = self.entries.lock().map_err(|e| e.to_string())?;
+        if let Some(entry) = entries.iter_mut().find(|e| e.id == id) {
+            entry.saved = !entry.saved;
+        }
+        Ok(())
+    }
+
+    pub async fn update_transcription_text(&self, id: i64, new_text: String) -> Result<(), String> {
+        let mut entries = self.entries.lock().map_err(|e| e.to_string())?;
+        if let Some(entry) = entries.iter_mut().find(|e| e.id == id) {
+            entry.text = new_text;
+        }
+        Ok(())
+    }
+
+    pub fn get_audio_file_path(&self, file_name: &str) -> PathBuf {
+        self.audio_dir.join(file_name)
+    }
+
+    pub async fn delete_entry(&self, id: i64) -> Result<(), String> {
+        let mut entries = self.entries.lock().map_err(|e| e.to_string())?;
+        entries.retain(|e| e.id != id);
+        Ok(())
+    }
+
+    pub async fn get_entry(&self, id: i64) -> Result<Option<HistoryEntry>, String> {
+        let entries = self.entries.lock().map_err(|e| e.to_string())?;
+        Ok(entries.iter().find(|e| e.id == id).cloned())
+    }
+
+    pub fn cleanup_old_entries(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Searches transcription text for `query`, narrowed by `filters`.
+    ///
+    /// This store keeps entries in memory, so the query is a case-insensitive
+    /// substring match; a SQLite-backed store would mirror this as an FTS5
+    /// virtual table over the text column, kept in sync on insert/update/delete,
+    /// so ranked matches don't require scanning the full history into memory.
+    pub async fn search_entries(
+        &self,
+        query: &str,
+        filters: HistorySearchFilters,
+    ) -> Result<Vec<HistoryEntry>, String> {
+        let entries = self.entries.lock().map_err(|e| e.to_string())?;
+        let query_lower = query.to_lowercase();
+
+        Ok(entries
+            .iter()
+            .filter(|e| query.is_empty() || e.text.to_lowercase().contains(&query_lower))
+            .filter(|e| !filters.saved_only || e.saved)
+            .filter(|e| filters.start_ms.map_or(true, |start| e.created_at >= start))
+            .filter(|e| filters.end_ms.map_or(true, |end| e.created_at <= end))
+            .filter(|e| {
+                filters
+                    .language
+                    .as_deref()
+                    .map_or(true, |lang| e.language.as_deref() == Some(lang))
+            })
+            .cloned()
+            .collect())
+    }
+}
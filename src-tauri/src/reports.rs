@@ -0,0 +1,115 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// A report is written whenever Gemini transcription fails on every model it
+// tries (`gemini_client::transcribe_with_timestamps`); post-processing
+// (`gemini_client::run_prompt`), streaming and Deepgram failures write no
+// report. Reports are written with or without the `report-errors` Cargo
+// feature; only their format depends on it. With the feature on, reports
+// are serialized as YAML (and the `serde_yaml` dependency is pulled in);
+// with it off, they fall back to JSON using only serde_json, which is
+// already a dependency.
+
+/// Everything needed to reproduce and diagnose an unrecoverable transcription
+/// failure, for the user to attach to a bug report.
+#[derive(Debug, Serialize)]
+pub struct FailureReport {
+    pub models_tried: Vec<String>,
+    pub http_status: Option<u16>,
+    pub raw_response: String,
+    pub prompt: String,
+    pub sample_count: usize,
+    pub sample_rate: u32,
+    pub duration_secs: f64,
+}
+
+impl FailureReport {
+    pub fn new(
+        models_tried: Vec<String>,
+        http_status: Option<u16>,
+        raw_response: String,
+        prompt: String,
+        sample_count: usize,
+        sample_rate: u32,
+    ) -> Self {
+        let duration_secs = if sample_rate == 0 {
+            0.0
+        } else {
+            sample_count as f64 / sample_rate as f64
+        };
+
+        Self {
+            models_tried,
+            http_status,
+            raw_response,
+            prompt,
+            sample_count,
+            sample_rate,
+            duration_secs,
+        }
+    }
+}
+
+fn reports_dir() -> PathBuf {
+    std::env::temp_dir().join("handy-reports")
+}
+
+#[cfg(feature = "report-errors")]
+fn serialize(report: &FailureReport) -> Result<String, String> {
+    serde_yaml::to_string(report).map_err(|e| format!("Failed to serialize report as YAML: {}", e))
+}
+
+#[cfg(not(feature = "report-errors"))]
+fn serialize(report: &FailureReport) -> Result<String, String> {
+    serde_json::to_string_pretty(report).map_err(|e| format!("Failed to serialize report as JSON: {}", e))
+}
+
+fn report_extension() -> &'static str {
+    if cfg!(feature = "report-errors") {
+        "yaml"
+    } else {
+        "json"
+    }
+}
+
+/// Writes a timestamped failure report to disk and returns its path.
+pub fn write_report(report: &FailureReport) -> Result<PathBuf, String> {
+    let dir = reports_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create reports dir: {}", e))?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("Failed to read system time: {}", e))?
+        .as_millis();
+
+    let path = dir.join(format!("report-{}.{}", timestamp, report_extension()));
+    let contents = serialize(report)?;
+    fs::write(&path, contents).map_err(|e| format!("Failed to write report: {}", e))?;
+
+    Ok(path)
+}
+
+/// Lists every report written by [`write_report`], newest first.
+pub fn list_reports() -> Result<Vec<PathBuf>, String> {
+    let dir = reports_dir();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read reports dir: {}", e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .collect();
+    paths.sort();
+    paths.reverse();
+
+    Ok(paths)
+}
+
+/// Reads a report's contents so it can be shown to the user or attached to a
+/// bug filing.
+pub fn open_report(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Failed to read report: {}", e))
+}
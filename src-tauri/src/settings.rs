@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use tauri::{AppHandle, Manager};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingRetentionPeriod {
+    Never,
+    PreserveLimit,
+    Days3,
+    Weeks2,
+    Months3,
+}
+
+impl Default for RecordingRetentionPeriod {
+    fn default() -> Self {
+        RecordingRetentionPeriod::PreserveLimit
+    }
+}
+
+/// Which cloud STT provider `transcribe_audio` should dispatch to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionBackendKind {
+    #[default]
+    Gemini,
+    Deepgram,
+}
+
+/// A user-editable post-processing action: a named prompt template that gets
+/// run against a history entry's transcription text (e.g. "summarize",
+/// "fix punctuation", "extract action items").
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostProcessAction {
+    pub id: String,
+    pub name: String,
+    pub prompt: String,
+    pub model: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub history_limit: usize,
+    #[serde(default)]
+    pub recording_retention_period: RecordingRetentionPeriod,
+    #[serde(default)]
+    pub post_process_api_keys: HashMap<String, String>,
+    #[serde(default)]
+    pub post_process_actions: Vec<PostProcessAction>,
+    #[serde(default)]
+    pub transcription_backend: TranscriptionBackendKind,
+    #[serde(default)]
+    pub gemini_api_key: String,
+    #[serde(default)]
+    pub deepgram_api_key: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            history_limit: 100,
+            recording_retention_period: RecordingRetentionPeriod::default(),
+            post_process_api_keys: HashMap::new(),
+            post_process_actions: Vec::new(),
+            transcription_backend: TranscriptionBackendKind::default(),
+            gemini_api_key: String::new(),
+            deepgram_api_key: String::new(),
+        }
+    }
+}
+
+fn settings_path(app: &AppHandle) -> std::path::PathBuf {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .expect("failed to resolve app data dir");
+    dir.join("settings.json")
+}
+
+pub fn get_settings(app: &AppHandle) -> Settings {
+    let path = settings_path(app);
+    fs::read_to_string(&path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn write_settings(app: &AppHandle, settings: Settings) {
+    let path = settings_path(app);
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(contents) = serde_json::to_string_pretty(&settings) {
+        let _ = fs::write(path, contents);
+    }
+}
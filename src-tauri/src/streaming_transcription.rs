@@ -0,0 +1,298 @@
+use std::collections::VecDeque;
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use futures_util::StreamExt;
+use log::{debug, error, info};
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::gemini_client::samples_to_wav_bytes;
+
+const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+/// Payload emitted on each newly-stabilized chunk of a live transcription.
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionPartial {
+    pub text: String,
+}
+
+/// Tracks which tokens of a growing, re-written candidate transcript are safe
+/// to surface to the user. Borrowed from AWS Transcribe streaming: a token is
+/// "stable" once it stays unchanged across the last `stability` candidates,
+/// and every stable token is emitted exactly once.
+pub struct Stabilizer {
+    window: VecDeque<Vec<String>>,
+    stability: usize,
+    emitted_index: usize,
+}
+
+impl Stabilizer {
+    pub fn new(stability: usize) -> Self {
+        Self {
+            window: VecDeque::new(),
+            stability: stability.max(1),
+            emitted_index: 0,
+        }
+    }
+
+    /// Feeds the latest full-transcript candidate (re-tokenized from the
+    /// model's latest output) and returns the tokens that just became stable
+    /// and haven't been emitted before.
+    pub fn push_candidate(&mut self, tokens: Vec<String>) -> Vec<String> {
+        self.window.push_back(tokens);
+        if self.window.len() > self.stability {
+            self.window.pop_front();
+        }
+
+        let stable_prefix_len = self.stable_prefix_len();
+        if stable_prefix_len <= self.emitted_index {
+            return Vec::new();
+        }
+
+        let newest = self.window.back().expect("just pushed a candidate");
+        let fresh = newest[self.emitted_index..stable_prefix_len].to_vec();
+        self.emitted_index = stable_prefix_len;
+        fresh
+    }
+
+    /// The length of the longest prefix shared by every candidate currently
+    /// in the window. Requires a full window so a single noisy candidate
+    /// can't prematurely stabilize a token.
+    fn stable_prefix_len(&self) -> usize {
+        if self.window.len() < self.stability {
+            return 0;
+        }
+
+        let mut candidates = self.window.iter();
+        let first = match candidates.next() {
+            Some(first) => first,
+            None => return 0,
+        };
+
+        let mut prefix_len = first.len();
+        for other in candidates {
+            prefix_len = prefix_len.min(other.len());
+            for (i, word) in other.iter().take(prefix_len).enumerate() {
+                if word != &first[i] {
+                    prefix_len = i;
+                    break;
+                }
+            }
+        }
+        prefix_len
+    }
+
+    /// Call once the stream ends to flush the remaining, never-stabilized
+    /// tail so nothing is lost.
+    pub fn flush(&mut self) -> Vec<String> {
+        match self.window.back() {
+            Some(newest) if self.emitted_index < newest.len() => {
+                let fresh = newest[self.emitted_index..].to_vec();
+                self.emitted_index = newest.len();
+                fresh
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split_whitespace().map(str::to_string).collect()
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamChunk {
+    candidates: Option<Vec<StreamCandidate>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamCandidate {
+    content: Option<StreamContent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamContent {
+    parts: Option<Vec<StreamPart>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamPart {
+    text: Option<String>,
+}
+
+/// Streams a transcription of `samples` from Gemini, emitting a
+/// `transcription-partial` event on `app` with each newly-stabilized chunk of
+/// text, and a final `transcription-partial` flush once the stream ends.
+///
+/// `stability` controls how many consecutive candidates a token must survive
+/// unchanged before it is considered final (see [`Stabilizer`]).
+pub async fn stream_transcribe(
+    app: &AppHandle,
+    api_key: &str,
+    samples: Vec<f32>,
+    sample_rate: u32,
+    language_hint: Option<String>,
+    stability: usize,
+) -> Result<(), String> {
+    if api_key.is_empty() {
+        return Err("Gemini API key is not configured. Please add your API key in Settings.".to_string());
+    }
+
+    let wav_bytes = samples_to_wav_bytes(&samples, sample_rate)?;
+    let base64_audio = STANDARD.encode(&wav_bytes);
+
+    let prompt = match language_hint {
+        Some(ref lang) if lang != "auto" => format!(
+            "Transcribe this audio. The language is {}. Return only the transcribed text, nothing else.",
+            lang
+        ),
+        _ => "Transcribe this audio. Return only the transcribed text, nothing else.".to_string(),
+    };
+
+    let url = format!(
+        "{}/models/gemini-2.5-flash:streamGenerateContent?alt=sse&key={}",
+        GEMINI_API_BASE, api_key
+    );
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    let body = serde_json::json!({
+        "contents": [{
+            "parts": [
+                { "text": prompt },
+                { "inline_data": { "mime_type": "audio/wav", "data": base64_audio } },
+            ],
+        }],
+    });
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(&url)
+        .headers(headers)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("API request failed with status {}: {}", status, text));
+    }
+
+    info!("Starting Gemini streaming transcription");
+
+    let mut stabilizer = Stabilizer::new(stability);
+    let mut accumulated = String::new();
+    let mut byte_stream = response.bytes_stream();
+    let mut buffer = String::new();
+
+    while let Some(chunk) = byte_stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Stream read error: {}", e))?;
+        buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+        while let Some(newline_pos) = buffer.find('\n') {
+            let line = buffer[..newline_pos].trim().to_string();
+            buffer.drain(..=newline_pos);
+
+            let Some(data) = line.strip_prefix("data: ") else {
+                continue;
+            };
+
+            let Ok(parsed) = serde_json::from_str::<StreamChunk>(data) else {
+                debug!("Skipping unparseable stream chunk: {}", data);
+                continue;
+            };
+
+            let delta = parsed
+                .candidates
+                .and_then(|c| c.into_iter().next())
+                .and_then(|c| c.content)
+                .and_then(|c| c.parts)
+                .and_then(|p| p.into_iter().next())
+                .and_then(|p| p.text)
+                .unwrap_or_default();
+
+            if delta.is_empty() {
+                continue;
+            }
+
+            accumulated.push_str(&delta);
+            let fresh = stabilizer.push_candidate(tokenize(&accumulated));
+            emit_partial(app, fresh);
+        }
+    }
+
+    let fresh = stabilizer.flush();
+    emit_partial(app, fresh);
+
+    Ok(())
+}
+
+fn emit_partial(app: &AppHandle, tokens: Vec<String>) {
+    if tokens.is_empty() {
+        return;
+    }
+    let text = tokens.join(" ");
+    if let Err(e) = app.emit("transcription-partial", TranscriptionPartial { text }) {
+        error!("Failed to emit transcription-partial event: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn nothing_emits_before_the_window_fills() {
+        let mut stabilizer = Stabilizer::new(3);
+        assert!(stabilizer.push_candidate(tokens(&["hello"])).is_empty());
+        assert!(stabilizer.push_candidate(tokens(&["hello", "world"])).is_empty());
+    }
+
+    #[test]
+    fn a_prefix_unchanged_across_the_window_stabilizes() {
+        let mut stabilizer = Stabilizer::new(3);
+        stabilizer.push_candidate(tokens(&["hello", "world"]));
+        stabilizer.push_candidate(tokens(&["hello", "world", "today"]));
+        let fresh = stabilizer.push_candidate(tokens(&["hello", "world", "today", "is"]));
+        // "hello" and "world" have now survived 3 consecutive candidates unchanged.
+        assert_eq!(fresh, tokens(&["hello", "world"]));
+    }
+
+    #[test]
+    fn a_revised_trailing_word_does_not_stabilize() {
+        let mut stabilizer = Stabilizer::new(3);
+        stabilizer.push_candidate(tokens(&["hello", "word"]));
+        stabilizer.push_candidate(tokens(&["hello", "word"]));
+        let fresh = stabilizer.push_candidate(tokens(&["hello", "world"]));
+        // Only "hello" survived unchanged; "word"/"world" disagree in the window.
+        assert_eq!(fresh, tokens(&["hello"]));
+    }
+
+    #[test]
+    fn each_stable_token_is_emitted_exactly_once() {
+        let mut stabilizer = Stabilizer::new(2);
+        let mut emitted = Vec::new();
+        emitted.extend(stabilizer.push_candidate(tokens(&["a"])));
+        emitted.extend(stabilizer.push_candidate(tokens(&["a", "b"])));
+        emitted.extend(stabilizer.push_candidate(tokens(&["a", "b", "c"])));
+        emitted.extend(stabilizer.push_candidate(tokens(&["a", "b", "c", "d"])));
+        // Every stable token shows up exactly once, never re-emitted.
+        assert_eq!(emitted, tokens(&["a", "b", "c"]));
+    }
+
+    #[test]
+    fn flush_emits_the_remaining_tail_exactly_once() {
+        let mut stabilizer = Stabilizer::new(5);
+        stabilizer.push_candidate(tokens(&["hello", "world"]));
+        assert!(!stabilizer.flush().is_empty());
+        assert!(stabilizer.flush().is_empty());
+    }
+}
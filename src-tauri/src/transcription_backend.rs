@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+
+use crate::deepgram_client::DeepgramBackend;
+use crate::gemini_client;
+use crate::managers::history::TranscriptWord;
+use crate::settings::{Settings, TranscriptionBackendKind};
+
+/// A pluggable cloud speech-to-text provider.
+///
+/// Implementors take raw PCM samples, encode them however their API
+/// expects, and return the transcribed text (or a human-readable error).
+#[async_trait]
+pub trait TranscriptionBackend: Send + Sync {
+    async fn transcribe(
+        &self,
+        samples: Vec<f32>,
+        sample_rate: u32,
+        language_hint: Option<String>,
+    ) -> Result<String, String>;
+}
+
+/// Transcribes `samples` with the provider selected in `settings`, also
+/// returning word-level timestamps so the caller can populate
+/// [`crate::managers::history::HistoryEntry::transcript`].
+///
+/// Deepgram is called through its [`TranscriptionBackend`] implementation;
+/// Gemini has no such implementation and is called directly through
+/// [`gemini_client::transcribe_with_timestamps`].
+///
+/// Only Gemini currently supports timestamps; other backends return the
+/// transcript with an empty word list.
+pub async fn transcribe_with_timestamps_with_settings(
+    settings: &Settings,
+    samples: Vec<f32>,
+    sample_rate: u32,
+    language_hint: Option<String>,
+) -> Result<(String, Vec<TranscriptWord>), String> {
+    match settings.transcription_backend {
+        TranscriptionBackendKind::Gemini => {
+            gemini_client::transcribe_with_timestamps(
+                &settings.gemini_api_key,
+                samples,
+                sample_rate,
+                language_hint,
+            )
+            .await
+        }
+        TranscriptionBackendKind::Deepgram => {
+            let text = DeepgramBackend::new(settings.deepgram_api_key.clone())
+                .transcribe(samples, sample_rate, language_hint)
+                .await?;
+            Ok((text, Vec::new()))
+        }
+    }
+}